@@ -1,34 +1,52 @@
 //! # A super light-weight dotenv library
-//! 
-//! With less than 20 lines of code of the core part, but meet most of the requirements of a dotenv. JUST KEEP IT SIMPLE!
+//!
+//! A tiny dotenv/config loader that meets most of the requirements of a dotenv without dragging in a big dependency tree. JUST KEEP IT SIMPLE!
 //!
 //! ## Examples
-//! 
+//!
 //! ```
 //! use dotconf::{init, init_with_path};
-//! 
+//!
 //! std::fs::write(".env", "a=b").unwrap();
 //! init().expect("Failed to load env conf file (default: .env)");
-//! 
+//!
 //! std::fs::write(".dotenvfile", "
 //!     a=b # This is a comment
 //!     b=32
 //!     c=true
 //! ").unwrap();
 //! init_with_path(".dotenvfile").expect("Failed to load from the specified env conf file");
-//! 
+//!
 //! // Read value with env::var with some simple type conversions
 //! let a = dotconf::var("a").to_string().unwrap();
 //! let b = dotconf::var("b").to_isize().unwrap();
 //! let c = dotconf::var("c").to_bool().unwrap();
 //! ```
 //!
+//! ## Also available
+//!
+//! - `${VAR}` / `$VAR` interpolation inside values, resolved against keys
+//!   defined earlier in the same file and then the process environment.
+//! - `init`/`init_with_path` only set a variable if it's currently absent
+//!   ("load" semantics); `init_override`/`init_override_with_path` always
+//!   set it ("overload" semantics).
+//! - `parse_reader`/`init_from_reader` parse any `Read` source, not just a
+//!   file path.
+//! - `from_path_as`/`from_pairs` deserialize a whole file straight into a
+//!   `#[derive(Deserialize)]` struct via serde.
+//! - Double- and single-quoted values, with `\n`/`\t`/`\"`/`\\` escapes and
+//!   multi-line spans inside double quotes.
+//! - `Dotconf` is a scoped handle over parsed pairs that never calls
+//!   `set_var`, for callers who don't want to touch the process
+//!   environment at all.
+//!
 //!
 
 
+use std::collections::HashMap;
 use std::fmt::{self, Display, Formatter};
 use std::fs::File;
-use std::io::{self, BufRead};
+use std::io::Read;
 use std::path::Path;
 use std::env::{self, set_var, VarError};
 
@@ -36,7 +54,7 @@ use std::env::{self, set_var, VarError};
 #[derive(Debug, Clone)]
 pub struct Error(String);
 impl Error {
-    pub fn to_string(self) -> String {
+    pub fn into_string(self) -> String {
         self.0
     }
 }
@@ -46,8 +64,10 @@ impl Display for Error {
     }
 }
 
-/// Load dotenv file with a default path.
-/// Use `init_with_path` to load from a specific file.
+/// Load dotenv file with a default path, without overriding variables
+/// already present in the process environment.
+/// Use `init_with_path` to load from a specific file, or `init_override`
+/// to let the file win over the shell.
 ///
 /// # Examples
 ///
@@ -59,7 +79,9 @@ impl Display for Error {
 /// ```
 pub fn init() -> Result<(), Error> { init_with_path(".env") }
 
-/// Load dotenv file with a specified path.
+/// Load dotenv file with a specified path, without overriding variables
+/// already present in the process environment (the "load" behavior). If a
+/// key appears more than once in the file, the first occurrence wins.
 ///
 /// # Examples
 ///
@@ -70,40 +92,279 @@ pub fn init() -> Result<(), Error> { init_with_path(".env") }
 /// dotconf::init_with_path(".dotenv_another").expect("Failed to load env conf file");
 /// ```
 pub fn init_with_path(path: &str) -> Result<(), Error>{
-    let pairs = parse_dotconf_file(path)?;
+    apply_pairs(parse_dotconf_file(path)?, false);
+    Ok(())
+}
+
+/// Load dotenv file with a default path, overriding variables already
+/// present in the process environment.
+/// Use `init_override_with_path` to load from a specific file.
+///
+/// # Examples
+///
+/// ```
+/// use dotconf;
+///
+/// std::fs::write(".env", "a=b").unwrap();
+/// dotconf::init_override().expect("Failed to load env conf file");
+/// ```
+pub fn init_override() -> Result<(), Error> { init_override_with_path(".env") }
+
+/// Load dotenv file with a specified path, overriding variables already
+/// present in the process environment (the "overload" behavior). If a key
+/// appears more than once in the file, the last occurrence wins.
+///
+/// # Examples
+///
+/// ```
+/// use dotconf;
+///
+/// std::fs::write(".dotenv_override", "a=b").unwrap();
+/// dotconf::init_override_with_path(".dotenv_override").expect("Failed to load env conf file");
+/// ```
+pub fn init_override_with_path(path: &str) -> Result<(), Error> {
+    apply_pairs(parse_dotconf_file(path)?, true);
+    Ok(())
+}
+
+/// Load dotenv-formatted content from any `Read` source, without overriding
+/// variables already present in the process environment. See `parse_reader`
+/// for the accepted sources and `init_with_path` for the override rules.
+///
+/// # Examples
+///
+/// ```
+/// use dotconf;
+///
+/// dotconf::init_from_reader("a=b".as_bytes()).expect("Failed to load env conf");
+/// ```
+pub fn init_from_reader<R: Read>(reader: R) -> Result<(), Error> {
+    apply_pairs(parse_reader(reader)?, false);
+    Ok(())
+}
+
+/// Apply parsed pairs to the process environment. When `override_existing`
+/// is `false` (the "load" semantics) a key is only set if it is currently
+/// absent; when `true` (the "overload" semantics) it is always set.
+fn apply_pairs(pairs: Vec<(String, String)>, override_existing: bool) {
     for (k, v) in pairs {
-        unsafe { set_var(k, v); }
+        if override_existing || env::var(&k).is_err() {
+            unsafe { set_var(k, v); }
+        }
     }
-    Ok(())
 }
 
-/// Parse dotenv file as key-value pairs
-/// use `#` to start a comment. 
-/// 
+/// Parse a dotenv file as key-value pairs.
+/// Opens `path` and delegates the actual parsing to `parse_reader`.
+///
 /// Sample:
-/// 
+///
 /// `url = https://xxxx.com  # Specify server address here`
-/// 
+///
+/// Values may reference other keys with `${NAME}` or `$NAME`. References are
+/// resolved first against keys defined earlier in the same file, then fall
+/// back to the process environment. Use `\$` to emit a literal `$`, and wrap
+/// a value in single quotes to skip expansion entirely.
 pub fn parse_dotconf_file(path: &str) -> Result<Vec<(String, String)>, Error> {
     let path = Path::new(path);
     let file = File::open(path).map_err(|err| Error(err.to_string()))?;
-    let reader = io::BufReader::new(file);
+    parse_reader(file)
+}
+
+/// Parse dotenv-formatted content from any `Read` source as key-value
+/// pairs, use `#` to start a comment outside of quotes.
+///
+/// This is the same parsing logic `parse_dotconf_file` uses, decoupled from
+/// the filesystem so it can load from stdin, an embedded string, an HTTP
+/// body, or a test buffer.
+///
+/// Values may be double-quoted, honoring `\n`, `\t`, `\"` and `\\` escapes
+/// and spanning multiple physical lines until the closing quote, or
+/// single-quoted, in which case the contents are taken literally with no
+/// escapes, expansion, or comment handling. Unquoted values keep the
+/// trim-and-truncate-at-`#` behavior.
+///
+/// A quoted value with no closing quote before end-of-input is not an
+/// error: the rest of the file is taken as its value, quote style and all.
+pub fn parse_reader<R: Read>(mut reader: R) -> Result<Vec<(String, String)>, Error> {
+    let mut content = String::new();
+    reader.read_to_string(&mut content).map_err(|err| Error(err.to_string()))?;
+    let chars: Vec<char> = content.chars().collect();
+    let len = chars.len();
     let mut pairs = Vec::new();
-    for line in reader.lines() {
-        match line {
-            Ok(text) => {
-                if let Some(line) = text.split('#').into_iter().next() {
-                    if let Some((k, v)) = line.split_once('=') {
-                        pairs.push((k.trim().to_string(), v.trim().to_string()));
-                    }
-                }
+    let mut known = HashMap::new();
+    let mut i = 0;
+    while i < len {
+        while i < len && (chars[i] == '\n' || chars[i] == ' ' || chars[i] == '\t' || chars[i] == '\r') {
+            i += 1;
+        }
+        if i >= len {
+            break;
+        }
+
+        let key_start = i;
+        while i < len && chars[i] != '=' && chars[i] != '\n' && chars[i] != '#' {
+            i += 1;
+        }
+        if i >= len || chars[i] != '=' {
+            // comment-only or malformed line: skip to the next one
+            while i < len && chars[i] != '\n' {
+                i += 1;
             }
-            _ => {}
+            continue;
+        }
+        let key = chars[key_start..i].iter().collect::<String>().trim().to_string();
+        i += 1; // skip '='
+        while i < len && (chars[i] == ' ' || chars[i] == '\t') {
+            i += 1;
         }
+
+        let (raw, quote, next) = read_value(&chars, i, len);
+        i = next;
+        let value = match quote {
+            Quote::Double => expand_vars(&unescape_double(&raw), &known),
+            Quote::Single => raw,
+            Quote::None => expand_vars(raw.split('#').next().unwrap_or("").trim(), &known),
+        };
+
+        known.insert(key.clone(), value.clone());
+        pairs.push((key, value));
     }
     Ok(pairs)
 }
 
+/// How a parsed value was quoted, which decides escape, expansion, and
+/// comment handling for it.
+enum Quote {
+    None,
+    Single,
+    Double,
+}
+
+/// Read a single value starting at `i`: a double- or single-quoted run
+/// (which may span multiple physical lines), or an unquoted run up to the
+/// end of the line. Returns the raw (still escaped, still quoted-literal)
+/// text, which quote style was used, and the index just past it.
+fn read_value(chars: &[char], mut i: usize, len: usize) -> (String, Quote, usize) {
+    if i < len && chars[i] == '"' {
+        i += 1;
+        let start = i;
+        let mut escaped = false;
+        while i < len {
+            if escaped {
+                escaped = false;
+            } else if chars[i] == '\\' {
+                escaped = true;
+            } else if chars[i] == '"' {
+                break;
+            }
+            i += 1;
+        }
+        let raw: String = chars[start..i].iter().collect();
+        if i < len {
+            i += 1; // skip closing quote
+        }
+        while i < len && chars[i] != '\n' {
+            i += 1;
+        }
+        (raw, Quote::Double, i)
+    } else if i < len && chars[i] == '\'' {
+        i += 1;
+        let start = i;
+        while i < len && chars[i] != '\'' {
+            i += 1;
+        }
+        let raw: String = chars[start..i].iter().collect();
+        if i < len {
+            i += 1; // skip closing quote
+        }
+        while i < len && chars[i] != '\n' {
+            i += 1;
+        }
+        (raw, Quote::Single, i)
+    } else {
+        let start = i;
+        while i < len && chars[i] != '\n' {
+            i += 1;
+        }
+        let raw: String = chars[start..i].iter().collect();
+        (raw, Quote::None, i)
+    }
+}
+
+/// Resolve `\n`, `\t`, `\"` and `\\` escapes inside a double-quoted value.
+/// Unrecognized escapes are left untouched, backslash included.
+fn unescape_double(raw: &str) -> String {
+    let mut out = String::new();
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('"') => out.push('"'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Expand `${NAME}` and `$NAME` references in `value`, resolving against
+/// `known` first and then the process environment. Unresolved names expand
+/// to an empty string. `\$` escapes to a literal `$`.
+fn expand_vars(value: &str, known: &HashMap<String, String>) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\\' && i + 1 < chars.len() && chars[i + 1] == '$' {
+            out.push('$');
+            i += 2;
+            continue;
+        }
+        if c == '$' && i + 1 < chars.len() {
+            if chars[i + 1] == '{' {
+                if let Some(end) = chars[i + 2..].iter().position(|&c| c == '}') {
+                    let name: String = chars[i + 2..i + 2 + end].iter().collect();
+                    out.push_str(&resolve_var(&name, known));
+                    i += 2 + end + 1;
+                    continue;
+                }
+            } else if chars[i + 1].is_ascii_alphanumeric() || chars[i + 1] == '_' {
+                let mut j = i + 1;
+                while j < chars.len() && (chars[j].is_ascii_alphanumeric() || chars[j] == '_') {
+                    j += 1;
+                }
+                let name: String = chars[i + 1..j].iter().collect();
+                out.push_str(&resolve_var(&name, known));
+                i = j;
+                continue;
+            }
+        }
+        out.push(c);
+        i += 1;
+    }
+    out
+}
+
+/// Resolve a referenced name against already-parsed keys, falling back to
+/// the process environment, and finally an empty string if unresolved.
+fn resolve_var(name: &str, known: &HashMap<String, String>) -> String {
+    match known.get(name) {
+        Some(v) => v.clone(),
+        None => env::var(name).unwrap_or_default(),
+    }
+}
+
 /// The wrapped env var result.
 ///
 /// # Examples
@@ -141,46 +402,19 @@ impl Display for Value {
 pub fn var(key: &str) -> Value { Value(env::var(key)) }
 impl Value {
     pub fn to_string(self) -> Option<String> {
-        match self.0 {
-            Ok(v) => Some(v),
-            Err(_) => None,
-        }
+        self.0.ok()
     }
 
     pub fn to_isize(self) -> Option<isize> {
-        match self.0 {
-            Ok(v) => {
-                match v.parse::<isize>() {
-                    Ok(v) => Some(v),
-                    Err(_) => None,
-                }
-            },
-            Err(_) => None,
-        }
+        self.0.ok().and_then(|v| v.parse::<isize>().ok())
     }
 
     pub fn to_usize(self) -> Option<usize> {
-        match self.0 {
-            Ok(v) => {
-                match v.parse::<usize>() {
-                    Ok(v) => Some(v),
-                    Err(_) => None,
-                }
-            },
-            Err(_) => None,
-        }
+        self.0.ok().and_then(|v| v.parse::<usize>().ok())
     }
 
     pub fn to_f64(self) -> Option<f64> {
-        match self.0 {
-            Ok(v) => {
-                match v.parse::<f64>() {
-                    Ok(v) => Some(v),
-                    Err(_) => None,
-                }
-            },
-            Err(_) => None,
-        }
+        self.0.ok().and_then(|v| v.parse::<f64>().ok())
     }
 
     pub fn to_bool(self) -> Option<bool> {
@@ -197,6 +431,272 @@ impl Value {
     }
 }
 
+/// A scoped config handle that owns its parsed pairs and never mutates the
+/// process environment, unlike `init`/`init_override` which call
+/// `set_var`. Build one from a path or reader, then resolve keys with
+/// `get`, which checks this handle's own map before falling back to
+/// `env::var` — the same indirection Cargo's `Config::get_env` uses in
+/// place of scattered `std::env::var` calls.
+///
+/// # Examples
+///
+/// ```
+/// use dotconf::Dotconf;
+///
+/// std::fs::write(".env_scoped", "a=b").unwrap();
+/// let cfg = Dotconf::from_path(".env_scoped").unwrap();
+/// assert_eq!(cfg.get("a").to_string(), Some("b".to_string()));
+/// std::fs::remove_file(".env_scoped").unwrap();
+/// ```
+pub struct Dotconf {
+    map: HashMap<String, String>,
+}
+
+impl Dotconf {
+    /// Build a `Dotconf` by parsing the file at `path`.
+    pub fn from_path(path: &str) -> Result<Self, Error> {
+        Ok(Dotconf { map: parse_dotconf_file(path)?.into_iter().collect() })
+    }
+
+    /// Build a `Dotconf` by parsing any `Read` source. See `parse_reader`
+    /// for the accepted sources.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, Error> {
+        Ok(Dotconf { map: parse_reader(reader)?.into_iter().collect() })
+    }
+
+    /// Resolve `key` from this handle's own map first, falling back to the
+    /// process environment via `env::var`.
+    pub fn get(&self, key: &str) -> Value {
+        match self.map.get(key) {
+            Some(v) => Value(Ok(v.clone())),
+            None => var(key),
+        }
+    }
+
+    /// Snapshot the process environment with this handle's own pairs
+    /// overlaid on top, without mutating either.
+    pub fn merged_with_env(&self) -> HashMap<String, String> {
+        let mut merged: HashMap<String, String> = env::vars().collect();
+        merged.extend(self.map.iter().map(|(k, v)| (k.clone(), v.clone())));
+        merged
+    }
+}
+
+/// Deserialize all parsed pairs from a dotenv file into a
+/// `#[derive(Deserialize)]` struct `T` in one call.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct AppConfig {
+///     port: u16,
+///     debug: Option<bool>,
+/// }
+///
+/// std::fs::write(".env_typed", "port = 8080").unwrap();
+/// let cfg: AppConfig = dotconf::from_path_as(".env_typed").unwrap();
+/// assert_eq!(cfg.port, 8080);
+/// assert_eq!(cfg.debug, None);
+/// std::fs::remove_file(".env_typed").unwrap();
+/// ```
+pub fn from_path_as<T: serde::de::DeserializeOwned>(path: &str) -> Result<T, Error> {
+    from_pairs(parse_dotconf_file(path)?)
+}
+
+/// Deserialize already-parsed pairs into a `#[derive(Deserialize)]` struct
+/// `T`. Duplicate keys keep the last occurrence.
+pub fn from_pairs<T: serde::de::DeserializeOwned>(pairs: Vec<(String, String)>) -> Result<T, Error> {
+    let map: HashMap<String, String> = pairs.into_iter().collect();
+    T::deserialize(PairsDeserializer { map: &map }).map_err(|err| Error(err.to_string()))
+}
+
+/// Error produced while deserializing parsed pairs into a typed struct,
+/// naming the offending key when one is known.
+#[derive(Debug)]
+pub struct DeError {
+    key: Option<String>,
+    message: String,
+}
+
+impl DeError {
+    fn for_key(key: &str, message: String) -> Self {
+        DeError { key: Some(key.to_string()), message }
+    }
+}
+
+impl Display for DeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match &self.key {
+            Some(key) => write!(f, "key `{}`: {}", key, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for DeError {}
+
+impl serde::de::Error for DeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        DeError { key: None, message: msg.to_string() }
+    }
+}
+
+/// Deserializer that presents a `HashMap<String, String>` as a serde map,
+/// so any `#[derive(Deserialize)]` struct can be built straight from parsed
+/// pairs.
+struct PairsDeserializer<'a> {
+    map: &'a HashMap<String, String>,
+}
+
+impl<'de, 'a> serde::de::Deserializer<'de> for PairsDeserializer<'a> {
+    type Error = DeError;
+
+    fn deserialize_any<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(PairsMapAccess { iter: self.map.iter(), current: None })
+    }
+
+    fn deserialize_struct<V: serde::de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct enum identifier ignored_any
+    }
+}
+
+struct PairsMapAccess<'a> {
+    iter: std::collections::hash_map::Iter<'a, String, String>,
+    current: Option<(&'a str, &'a str)>,
+}
+
+impl<'de, 'a> serde::de::MapAccess<'de> for PairsMapAccess<'a> {
+    type Error = DeError;
+
+    fn next_key_seed<K: serde::de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+        match self.iter.next() {
+            Some((k, v)) => {
+                self.current = Some((k.as_str(), v.as_str()));
+                seed.deserialize(KeyDeserializer(k.as_str())).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: serde::de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+        let (key, value) = self.current.take().expect("next_value_seed called before next_key_seed");
+        seed.deserialize(ValueDeserializer { key, value })
+    }
+}
+
+/// Deserializer for a single map key, always presented as a string.
+struct KeyDeserializer<'a>(&'a str);
+
+impl<'de, 'a> serde::de::Deserializer<'de> for KeyDeserializer<'a> {
+    type Error = DeError;
+
+    fn deserialize_any<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_str(self.0)
+    }
+
+    fn deserialize_identifier<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_str(self.0)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum ignored_any
+    }
+}
+
+/// Deserializer for a single map value, coercing the raw string into
+/// whatever primitive type the target field asks for.
+struct ValueDeserializer<'a> {
+    key: &'a str,
+    value: &'a str,
+}
+
+impl<'a> ValueDeserializer<'a> {
+    fn parse<T: std::str::FromStr>(&self) -> Result<T, DeError>
+    where
+        T::Err: Display,
+    {
+        self.value.parse::<T>().map_err(|err| DeError::for_key(self.key, err.to_string()))
+    }
+}
+
+macro_rules! deserialize_number {
+    ($($method:ident => $visit:ident : $ty:ty),* $(,)?) => {
+        $(
+            fn $method<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                visitor.$visit(self.parse::<$ty>()?)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> serde::de::Deserializer<'de> for ValueDeserializer<'a> {
+    type Error = DeError;
+
+    fn deserialize_any<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_str(self.value)
+    }
+
+    fn deserialize_option<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_bool<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value.to_lowercase().as_str() {
+            "true" => visitor.visit_bool(true),
+            "false" => visitor.visit_bool(false),
+            _ => Err(DeError::for_key(self.key, format!("invalid bool value: {}", self.value))),
+        }
+    }
+
+    deserialize_number! {
+        deserialize_i8 => visit_i8: i8,
+        deserialize_i16 => visit_i16: i16,
+        deserialize_i32 => visit_i32: i32,
+        deserialize_i64 => visit_i64: i64,
+        deserialize_u8 => visit_u8: u8,
+        deserialize_u16 => visit_u16: u16,
+        deserialize_u32 => visit_u32: u32,
+        deserialize_u64 => visit_u64: u64,
+        deserialize_i128 => visit_i128: i128,
+        deserialize_u128 => visit_u128: u128,
+        deserialize_f32 => visit_f32: f32,
+        deserialize_f64 => visit_f64: f64,
+    }
+
+    fn deserialize_str<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_str(self.value)
+    }
+
+    fn deserialize_string<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_string(self.value.to_string())
+    }
+
+    serde::forward_to_deserialize_any! {
+        char bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs;
@@ -218,4 +718,134 @@ mod tests {
 
         fs::remove_file(".env").unwrap();
     }
+
+    #[test]
+    fn it_expands_variables() {
+        use crate::parse_dotconf_file;
+
+        unsafe { std::env::set_var("DOTCONF_TEST_HOST", "env-host"); }
+        let raw = "
+        base = https://x.com
+        url = ${base}/api
+        escaped = \\$base
+        literal = '${base}'
+        fallback = $DOTCONF_TEST_HOST
+        ";
+        fs::write(".env_expand", raw).unwrap();
+        let pairs = parse_dotconf_file(".env_expand").unwrap();
+        assert_eq!(pairs[1], ("url".to_string(), "https://x.com/api".to_string()));
+        assert_eq!(pairs[2], ("escaped".to_string(), "$base".to_string()));
+        assert_eq!(pairs[3], ("literal".to_string(), "${base}".to_string()));
+        assert_eq!(pairs[4], ("fallback".to_string(), "env-host".to_string()));
+
+        fs::remove_file(".env_expand").unwrap();
+    }
+
+    #[test]
+    fn it_does_not_override_by_default_but_overload_does() {
+        use crate::{init_override_with_path, init_with_path};
+
+        unsafe { std::env::set_var("DOTCONF_TEST_LOAD", "from-shell"); }
+        fs::write(".env_load", "DOTCONF_TEST_LOAD=from-file").unwrap();
+        init_with_path(".env_load").unwrap();
+        assert_eq!(var("DOTCONF_TEST_LOAD").to_string(), Some("from-shell".to_string()));
+
+        init_override_with_path(".env_load").unwrap();
+        assert_eq!(var("DOTCONF_TEST_LOAD").to_string(), Some("from-file".to_string()));
+
+        fs::remove_file(".env_load").unwrap();
+    }
+
+    #[test]
+    fn it_keeps_first_occurrence_on_load_and_last_on_overload() {
+        use crate::{init_override_with_path, init_with_path};
+
+        fs::write(".env_dup", "DOTCONF_TEST_DUP=first\nDOTCONF_TEST_DUP=second\n").unwrap();
+
+        init_with_path(".env_dup").unwrap();
+        assert_eq!(var("DOTCONF_TEST_DUP").to_string(), Some("first".to_string()));
+
+        init_override_with_path(".env_dup").unwrap();
+        assert_eq!(var("DOTCONF_TEST_DUP").to_string(), Some("second".to_string()));
+
+        fs::remove_file(".env_dup").unwrap();
+    }
+
+    #[test]
+    fn it_parses_from_a_reader() {
+        use crate::{init_from_reader, parse_reader};
+
+        let pairs = parse_reader("a = hi\nb = 32 # comment\n".as_bytes()).unwrap();
+        assert_eq!(pairs, vec![
+            ("a".to_string(), "hi".to_string()),
+            ("b".to_string(), "32".to_string()),
+        ]);
+
+        init_from_reader("DOTCONF_TEST_READER=from-reader".as_bytes()).unwrap();
+        assert_eq!(var("DOTCONF_TEST_READER").to_string(), Some("from-reader".to_string()));
+    }
+
+    #[test]
+    fn it_deserializes_into_a_typed_struct() {
+        use serde::Deserialize;
+
+        #[derive(Deserialize)]
+        struct AppConfig {
+            port: u16,
+            name: String,
+            debug: Option<bool>,
+            timeout: Option<u32>,
+        }
+
+        let pairs = vec![
+            ("port".to_string(), "8080".to_string()),
+            ("name".to_string(), "svc".to_string()),
+            ("debug".to_string(), "true".to_string()),
+        ];
+        let cfg: AppConfig = crate::from_pairs(pairs).unwrap();
+        assert_eq!(cfg.port, 8080);
+        assert_eq!(cfg.name, "svc");
+        assert_eq!(cfg.debug, Some(true));
+        assert_eq!(cfg.timeout, None);
+    }
+
+    #[test]
+    fn it_parses_quoted_and_multiline_values() {
+        use crate::parse_reader;
+
+        let raw = "hashed = \"a # b\\nc\"\nmulti = \"line one\nline two\"\nraw = 'no $expand # here'\n";
+        let pairs = parse_reader(raw.as_bytes()).unwrap();
+        assert_eq!(pairs, vec![
+            ("hashed".to_string(), "a # b\nc".to_string()),
+            ("multi".to_string(), "line one\nline two".to_string()),
+            ("raw".to_string(), "no $expand # here".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn it_takes_the_rest_of_the_input_as_the_value_when_a_quote_is_unterminated() {
+        use crate::parse_reader;
+
+        let raw = "opening = \"never closed\nnext = ignored";
+        let pairs = parse_reader(raw.as_bytes()).unwrap();
+        assert_eq!(pairs, vec![
+            ("opening".to_string(), "never closed\nnext = ignored".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn it_resolves_scoped_config_without_touching_the_process_env() {
+        use crate::Dotconf;
+
+        unsafe { std::env::set_var("DOTCONF_TEST_SCOPED_SHELL", "from-shell"); }
+        let cfg = Dotconf::from_reader("DOTCONF_TEST_SCOPED_FILE=from-file".as_bytes()).unwrap();
+
+        assert_eq!(cfg.get("DOTCONF_TEST_SCOPED_FILE").to_string(), Some("from-file".to_string()));
+        assert_eq!(cfg.get("DOTCONF_TEST_SCOPED_SHELL").to_string(), Some("from-shell".to_string()));
+        assert!(var("DOTCONF_TEST_SCOPED_FILE").to_string().is_none());
+
+        let merged = cfg.merged_with_env();
+        assert_eq!(merged.get("DOTCONF_TEST_SCOPED_FILE"), Some(&"from-file".to_string()));
+        assert_eq!(merged.get("DOTCONF_TEST_SCOPED_SHELL"), Some(&"from-shell".to_string()));
+    }
 }